@@ -0,0 +1,45 @@
+//! Fixed-width base36 encoding used by the compact output formats.
+//!
+//! Encoding every field to a fixed width is what keeps compact IDs lexicographically
+//! sortable: a shorter encoded value would sort before a longer one even if its numeric
+//! value is larger, so every caller must pick a width wide enough for its value range and
+//! zero-pad up to it.
+
+const ALPHABET: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Encodes `value` as lowercase base36, zero-padded to `width` characters.
+///
+/// # Panics
+/// Panics if `value` does not fit in `width` base36 digits.
+pub(crate) fn encode(value: u64, width: usize) -> String {
+    let mut digits = vec![b'0'; width];
+    let mut remaining = value;
+    for slot in digits.iter_mut().rev() {
+        *slot = ALPHABET[(remaining % 36) as usize];
+        remaining /= 36;
+    }
+    assert_eq!(remaining, 0, "value does not fit in {} base36 digits", width);
+    String::from_utf8(digits).expect("base36 alphabet is ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode;
+
+    #[test]
+    fn test_encode_zero_pads_to_width() {
+        assert_eq!(encode(0, 4), "0000");
+        assert_eq!(encode(35, 4), "000z");
+        assert_eq!(encode(36, 4), "0010");
+    }
+
+    #[test]
+    fn test_encode_preserves_numeric_order() {
+        let mut prev = encode(0, 6);
+        for value in [1u64, 2, 35, 36, 37, 1_000, 999_999] {
+            let next = encode(value, 6);
+            assert!(next > prev, "{} should sort after {}", next, prev);
+            prev = next;
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use std::fmt;
+
+/// Errors returned when parsing a generated ID back into its components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SquidError {
+    /// The input did not split into the expected number of `-`-delimited fields.
+    MalformedId,
+    /// The counter field was not the expected fixed width.
+    InvalidCounterWidth,
+    /// The counter field could not be parsed as a number.
+    InvalidCounter,
+    /// The timestamp field could not be parsed as a number.
+    InvalidTimestamp,
+}
+
+impl fmt::Display for SquidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedId => write!(f, "id is missing one or more `-`-delimited fields"),
+            Self::InvalidCounterWidth => write!(f, "counter field is not the expected fixed width"),
+            Self::InvalidCounter => write!(f, "counter field is not a valid number"),
+            Self::InvalidTimestamp => write!(f, "timestamp field is not a valid number"),
+        }
+    }
+}
+
+impl std::error::Error for SquidError {}
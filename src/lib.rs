@@ -1,5 +1,9 @@
-mod versions;
+mod base36;
+mod error;
+pub mod versions;
+pub use error::SquidError;
 pub use versions::v0::SQUIDv0;
+pub use versions::v1::SQUIDv1;
 
 pub trait SQUID {
     fn generate(&mut self) -> String;
@@ -10,3 +14,9 @@ impl SQUID for SQUIDv0 {
         Self::generate(self)
     }
 }
+
+impl SQUID for SQUIDv1 {
+    fn generate(&mut self) -> String {
+        Self::generate(self)
+    }
+}
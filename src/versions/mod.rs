@@ -0,0 +1,2 @@
+pub mod v0;
+pub mod v1;
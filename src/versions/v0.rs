@@ -1,21 +1,154 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::SquidError;
+
+/// How long the overflow/clock-regression spin in [`Context::advance`] sleeps between clock
+/// polls. Keeps the wait from pegging a core for however long the clock takes to catch up,
+/// at the cost of sub-millisecond added latency on the (rare) call that has to wait.
+const SPIN_POLL_INTERVAL: Duration = Duration::from_micros(100);
+
+/// Number of low bits of a [`Context`]'s packed state reserved for the counter.
+const COUNTER_BITS: u32 = 20;
+const COUNTER_MASK: u64 = (1 << COUNTER_BITS) - 1;
+
+/// Fixed width of a formatted counter field, as produced by `format!("{:04}", counter)`.
+const COUNTER_WIDTH: usize = 4;
+
+/// The highest counter value that fits the formatted 4-digit counter field.
+///
+/// Once a [`Context`] has handed out counter value `MAX_COUNTER_PER_MS` within the same
+/// millisecond, the next call spins until the clock ticks over to the next millisecond rather than
+/// overflow the field. This is the guaranteed per-generator throughput ceiling: at most
+/// `MAX_COUNTER_PER_MS + 1` IDs per millisecond.
+pub const MAX_COUNTER_PER_MS: u64 = 9_999;
+
+/// Width, in base36 characters, of the timestamp field in [`SQUIDv0::generate_compact`].
+///
+/// `36.pow(9)` milliseconds is about 3,200 years, comfortably more than any offset from a
+/// reasonable epoch will need for the foreseeable future.
+const COMPACT_TIMESTAMP_WIDTH: usize = 9;
+
+/// Width, in base36 characters, of the counter field in [`SQUIDv0::generate_compact`].
+///
+/// `36.pow(3)` is 46,656, more than [`MAX_COUNTER_PER_MS`] can ever reach.
+const COMPACT_COUNTER_WIDTH: usize = 3;
+
+/// Default epoch for [`SQUIDv0::generate_compact`]: 2020-01-01T00:00:00Z, in milliseconds
+/// since the Unix epoch. Offsetting from a recent epoch instead of 1970 keeps the encoded
+/// timestamp field short for IDs generated in the foreseeable future.
+pub const DEFAULT_COMPACT_EPOCH_MS: u64 = 1_577_836_800_000;
+
+/// Process-wide generation state that one or more [`SQUIDv0`] instances can share.
+///
+/// Modeled on the `Context` type from the `uuid` crate's v1 implementation: the last
+/// timestamp and counter are packed into a single [`AtomicU64`] so concurrent generators
+/// can claim the next `(timestamp, counter)` pair with one compare-and-swap instead of a
+/// `Mutex`. Wrap a `Context` in an [`Arc`] and hand clones of it to [`SQUIDv0::with_context`]
+/// to make every generator sharing it produce distinct, process-wide-unique IDs.
+pub struct Context {
+    state: AtomicU64,
+}
+
+impl Context {
+    /// Creates a fresh context with no recorded timestamp.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: AtomicU64::new(0),
+        }
+    }
+
+    fn pack(timestamp: u64, counter: u64) -> u64 {
+        (timestamp << COUNTER_BITS) | (counter & COUNTER_MASK)
+    }
+
+    fn unpack(packed: u64) -> (u64, u64) {
+        (packed >> COUNTER_BITS, packed & COUNTER_MASK)
+    }
+
+    fn now_millis() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_millis() as u64
+    }
+
+    /// Atomically advances the context to the current millisecond, returning the
+    /// `(timestamp, counter)` pair the caller should use for its next generated ID.
+    ///
+    /// If the current millisecond is strictly after the last recorded timestamp, the
+    /// timestamp is updated and the counter resets to `0`. Otherwise — the millisecond is
+    /// unchanged, or the clock has moved *backward* (an NTP step or a suspend/resume jump)
+    /// — `last_timestamp` is kept and the counter is incremented instead, so IDs stay
+    /// monotonically non-decreasing even across clock regressions. The update is retried on
+    /// contention, so callers never observe a torn read of the packed state.
+    ///
+    /// If the counter would exceed [`MAX_COUNTER_PER_MS`] before the clock moves past
+    /// `last_timestamp`, this sleeps for [`SPIN_POLL_INTERVAL`] between clock re-samples
+    /// until it does, then resumes at counter `0` rather than overflow the counter's
+    /// formatted width or peg a core while waiting out a clock regression.
+    fn advance(&self) -> (u64, u64) {
+        let mut now = Self::now_millis();
+        let mut current = self.state.load(Ordering::Relaxed);
+        loop {
+            let (last_timestamp, counter) = Self::unpack(current);
+            let (next_timestamp, next_counter) = if now > last_timestamp {
+                (now, 0)
+            } else if counter >= MAX_COUNTER_PER_MS {
+                loop {
+                    thread::sleep(SPIN_POLL_INTERVAL);
+                    now = Self::now_millis();
+                    if now > last_timestamp {
+                        break;
+                    }
+                }
+                (now, 0)
+            } else {
+                (last_timestamp, counter + 1)
+            };
+            let next = Self::pack(next_timestamp, next_counter);
+            match self
+                .state
+                .compare_exchange_weak(current, next, Ordering::SeqCst, Ordering::Relaxed)
+            {
+                Ok(_) => return (next_timestamp, next_counter),
+                Err(actual) => {
+                    current = actual;
+                    now = Self::now_millis();
+                }
+            }
+        }
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// Struct representing the version 0 implementation of the SQUID ID generation system.
-/// 
+///
 /// The `SQUIDv0` struct generates unique, sortable IDs using a combination of the device UUID,
-/// the current timestamp, and an internal counter to handle rapid successive calls.
+/// the current timestamp, and an internal counter to handle rapid successive calls. The
+/// timestamp and counter live in a shared [`Context`], so sharing that `Context` (through
+/// clones of an `Arc<Context>`) across `SQUIDv0` instances (including ones used from different threads) keeps every generated ID
+/// unique without requiring a `Mutex`.
 ///
 /// # Warning
 ///
 /// **This v0 implementation must not be used in applications where privacy of the device is critical, such as exposing the ID to the internet, because the device UUID is exposed.**
 pub struct SQUIDv0 {
     device_uuid: String,
-    counter: usize,
-    last_timestamp: u128,
+    context: Arc<Context>,
+    compact_epoch_ms: u64,
 }
 
 impl SQUIDv0 {
-    /// Creates a new `SQUIDv0` instance.
+    /// Creates a new `SQUIDv0` instance with its own private [`Context`].
     ///
     /// The device UUID is retrieved using the `machine_uuid` library. If the retrieval fails,
     /// a default UUID of "00000000-0000-0000-0000-000000000000" is used.
@@ -27,19 +160,52 @@ impl SQUIDv0 {
     ///
     /// let squid = SQUIDv0::new(None);
     /// ```
-     #[must_use]
+    #[must_use]
     pub fn new(device_uuid: Option<&str>) -> Self {
+        Self::with_context(device_uuid, Arc::new(Context::new()))
+    }
+
+    /// Creates a new `SQUIDv0` backed by an explicit, possibly shared, [`Context`].
+    ///
+    /// Pass clones of the same `Arc<Context>` to multiple `SQUIDv0` instances to let them
+    /// share one generation sequence: every `generate` call resolves to a single atomic
+    /// compare-and-swap against the packed `(timestamp, counter)` word in the `Context`,
+    /// so two generators sharing it can never emit the same ID, even across threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use sortable_quick_unique_id::versions::v0::Context;
+    /// use sortable_quick_unique_id::SQUIDv0;
+    ///
+    /// let context = Arc::new(Context::new());
+    /// let mut a = SQUIDv0::with_context(None, Arc::clone(&context));
+    /// let mut b = SQUIDv0::with_context(None, context);
+    /// ```
+    #[must_use]
+    pub fn with_context(device_uuid: Option<&str>, context: Arc<Context>) -> Self {
         let uuid = device_uuid.map_or_else(
             || machine_uuid::get().unwrap_or_else(|_| "00000000-0000-0000-0000-000000000000".to_string()),
             |s| s.to_string(),
         );
         Self {
             device_uuid: uuid,
-            counter: 0,
-            last_timestamp: 0,
+            context,
+            compact_epoch_ms: DEFAULT_COMPACT_EPOCH_MS,
         }
     }
 
+    /// Sets the epoch that [`Self::generate_compact`] measures its timestamp field from.
+    ///
+    /// Defaults to [`DEFAULT_COMPACT_EPOCH_MS`]. Choosing an epoch close to when IDs start
+    /// being generated keeps the encoded offset, and therefore the ID, shorter.
+    #[must_use]
+    pub fn with_compact_epoch_ms(mut self, epoch_ms: u64) -> Self {
+        self.compact_epoch_ms = epoch_ms;
+        self
+    }
+
     /// Generates a unique ID.
     ///
     /// The ID is a combination of the device UUID, the current timestamp in milliseconds,
@@ -48,13 +214,16 @@ impl SQUIDv0 {
     /// # How it works
     ///
     /// 1. The current timestamp is retrieved in milliseconds since the Unix epoch.
-    /// 2. If the timestamp is the same as the last generated timestamp, the counter is incremented.
-    /// 3. If the timestamp is different, the counter is reset to 0.
-    /// 4. The ID is formatted as "DeviceUUID-Timestamp-Counter".
+    /// 2. The timestamp and counter are advanced atomically against the shared `Context`: if
+    ///    the timestamp is the same as (or earlier than) the last generated timestamp, the
+    ///    counter is incremented; if the timestamp is later, the counter is reset to 0. If the
+    ///    counter has reached [`MAX_COUNTER_PER_MS`] for the current millisecond, this spins
+    ///    until the clock ticks forward instead of wrapping the counter.
+    /// 3. The ID is formatted as "DeviceUUID-Timestamp-Counter".
     ///
-    /// # Panics
-    /// The generate function could panic if there was a unsigned integer overflow of the timestamp,
-    /// which is highly unlikely to happen for a very long time(several billon years or more).
+    /// IDs stay monotonically non-decreasing even if the system clock moves backward (an NTP
+    /// step or a suspend/resume jump): a backward jump is treated the same as an unchanged
+    /// millisecond, so `generate` never panics or emits an out-of-order timestamp.
     ///
     /// # Examples
     ///
@@ -66,29 +235,126 @@ impl SQUIDv0 {
     /// println!("Generated ID: {}", id);
     /// ```
     pub fn generate(&mut self) -> String {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_millis();
-
-        if timestamp == self.last_timestamp {
-            self.counter += 1;
-        } else {
-            self.counter = 0;
-            self.last_timestamp = timestamp;
-        }
+        let (timestamp, counter) = self.context.advance();
 
-        let counter_str = format!("{:04}", self.counter);
+        let counter_str = format!("{:04}", counter);
 
         // Format: DeviceUUID-Timestamp-Counter
         format!("{}-{}-{}", self.device_uuid, timestamp, counter_str)
     }
+
+    /// Generates a unique ID in a shorter, still-sortable, compact format.
+    ///
+    /// Carries the same device UUID, timestamp, and counter as [`Self::generate`], but
+    /// encodes the timestamp as its millisecond offset from `compact_epoch_ms` (see
+    /// [`Self::with_compact_epoch_ms`]) and the counter, both in fixed-width, zero-padded
+    /// base36. Fixed widths preserve lexicographic sort order, borrowing the approach cuid2
+    /// uses for its own base36-encoded timestamp.
+    ///
+    /// # Panics
+    /// Panics if the current timestamp predates `compact_epoch_ms`, or if the millisecond
+    /// offset from the epoch no longer fits [`COMPACT_TIMESTAMP_WIDTH`] base36 digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sortable_quick_unique_id::SQUIDv0;
+    ///
+    /// let mut squid = SQUIDv0::new(None);
+    /// let id = squid.generate_compact();
+    /// println!("Generated compact ID: {}", id);
+    /// ```
+    pub fn generate_compact(&mut self) -> String {
+        let (timestamp, counter) = self.context.advance();
+
+        let offset_ms = timestamp
+            .checked_sub(self.compact_epoch_ms)
+            .expect("timestamp predates the compact epoch");
+        let timestamp_str = crate::base36::encode(offset_ms, COMPACT_TIMESTAMP_WIDTH);
+        let counter_str = crate::base36::encode(counter, COMPACT_COUNTER_WIDTH);
+
+        // Format: DeviceUUID-Timestamp-Counter, with Timestamp/Counter in base36.
+        format!("{}-{}-{}", self.device_uuid, timestamp_str, counter_str)
+    }
+
+    /// Parses an ID produced by [`Self::generate`] back into its [`SquidParts`].
+    ///
+    /// Splits from the right on `-` so that a device UUID containing its own `-` separators
+    /// (the usual UUID format) is still recovered as a single field, then validates the
+    /// counter field's fixed width before parsing the timestamp and counter as numbers.
+    ///
+    /// Note this does not parse IDs produced by [`Self::generate_compact`], whose timestamp
+    /// and counter fields are base36, not decimal.
+    ///
+    /// # Errors
+    /// Returns a [`SquidError`] if the id is missing a `-`-delimited field, the counter field
+    /// is not exactly [`COUNTER_WIDTH`] characters, or a field fails to parse as a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sortable_quick_unique_id::{SQUID, SQUIDv0};
+    ///
+    /// let mut squid = SQUIDv0::new(None);
+    /// let id = squid.generate();
+    /// let parts = SQUIDv0::parse(&id).unwrap();
+    /// assert_eq!(format!("{}", id), format!(
+    ///     "{}-{}-{:04}",
+    ///     parts.device_uuid, parts.timestamp, parts.counter
+    /// ));
+    /// ```
+    pub fn parse(id: &str) -> Result<SquidParts, SquidError> {
+        let mut fields = id.rsplitn(3, '-');
+        let counter_str = fields.next().ok_or(SquidError::MalformedId)?;
+        let timestamp_str = fields.next().ok_or(SquidError::MalformedId)?;
+        let device_uuid = fields.next().ok_or(SquidError::MalformedId)?;
+
+        if counter_str.len() != COUNTER_WIDTH {
+            return Err(SquidError::InvalidCounterWidth);
+        }
+
+        let timestamp = timestamp_str
+            .parse::<u128>()
+            .map_err(|_| SquidError::InvalidTimestamp)?;
+        let counter = counter_str
+            .parse::<usize>()
+            .map_err(|_| SquidError::InvalidCounter)?;
+
+        Ok(SquidParts {
+            device_uuid: device_uuid.to_string(),
+            timestamp,
+            counter,
+        })
+    }
+}
+
+/// The components of an ID parsed by [`SQUIDv0::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SquidParts {
+    /// The device UUID the ID was generated with.
+    pub device_uuid: String,
+    /// The ID's timestamp, in milliseconds since the Unix epoch.
+    pub timestamp: u128,
+    /// The ID's counter, disambiguating IDs generated within the same millisecond.
+    pub counter: usize,
+}
+
+impl SquidParts {
+    /// Returns [`Self::timestamp`] as a [`SystemTime`], for callers that want to sort,
+    /// shard, or time-filter records without keeping a separate stored timestamp.
+    #[must_use]
+    pub fn timestamp_as_system_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.timestamp as u64)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::SQUIDv0;
+    use super::{Context, MAX_COUNTER_PER_MS, SQUIDv0};
     use std::collections::HashSet;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::thread;
 
     #[test]
     fn test_generate_unique_ids() {
@@ -104,4 +370,92 @@ mod tests {
 
         assert_eq!(generated_ids.len(), total_ids, "Not all IDs are unique");
     }
+
+    #[test]
+    fn test_shared_context_is_unique_across_threads() {
+        let context = Arc::new(Context::new());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let mut squid = SQUIDv0::with_context(None, Arc::clone(&context));
+                thread::spawn(move || (0..10_000).map(|_| squid.generate()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut generated_ids = HashSet::new();
+        for handle in handles {
+            for id in handle.join().expect("thread panicked") {
+                assert!(generated_ids.insert(id.clone()), "Duplicate ID found: {}", id);
+            }
+        }
+
+        assert_eq!(generated_ids.len(), 80_000, "Not all IDs are unique");
+    }
+
+    #[test]
+    fn test_counter_overflow_waits_for_next_millisecond() {
+        let context = Context::new();
+        let now = Context::now_millis();
+        context
+            .state
+            .store(Context::pack(now, MAX_COUNTER_PER_MS), Ordering::Relaxed);
+
+        let (timestamp, counter) = context.advance();
+
+        assert!(timestamp > now, "should have waited for the next millisecond");
+        assert_eq!(counter, 0);
+    }
+
+    #[test]
+    fn test_generate_compact_is_shorter_and_sortable() {
+        let mut squid = SQUIDv0::new(Some("device"));
+        let mut previous = None;
+
+        for _ in 0..1_000 {
+            let id = squid.generate_compact();
+            assert!(id.len() < "device-1700000000000-0000".len());
+            if let Some(previous) = previous {
+                assert!(id >= previous, "{} should not sort before {}", id, previous);
+            }
+            previous = Some(id);
+        }
+    }
+
+    #[test]
+    fn test_parse_round_trips_with_generate() {
+        let mut squid = SQUIDv0::new(Some("00000000-0000-0000-0000-000000000001"));
+        let id = squid.generate();
+
+        let parts = SQUIDv0::parse(&id).expect("id should parse");
+
+        assert_eq!(parts.device_uuid, "00000000-0000-0000-0000-000000000001");
+        assert_eq!(
+            id,
+            format!("{}-{}-{:04}", parts.device_uuid, parts.timestamp, parts.counter)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_ids() {
+        assert_eq!(SQUIDv0::parse("not-an-id").unwrap_err(), super::SquidError::InvalidCounterWidth);
+        assert_eq!(SQUIDv0::parse("device-123-45").unwrap_err(), super::SquidError::InvalidCounterWidth);
+        assert_eq!(SQUIDv0::parse("device-abcd-0001").unwrap_err(), super::SquidError::InvalidTimestamp);
+        assert_eq!(SQUIDv0::parse("onlyonefield").unwrap_err(), super::SquidError::MalformedId);
+    }
+
+    #[test]
+    fn test_advance_clamps_backward_clock_movement() {
+        let context = Context::new();
+        let now = Context::now_millis();
+        // Simulate a clock that has already recorded a timestamp ahead of "now", as if an
+        // NTP step or suspend/resume jump moved the system clock backward.
+        let future = now + 60_000;
+        context
+            .state
+            .store(Context::pack(future, 0), Ordering::Relaxed);
+
+        let (timestamp, counter) = context.advance();
+
+        assert_eq!(timestamp, future, "timestamp must not move backward");
+        assert_eq!(counter, 1, "counter should advance instead of resetting");
+    }
 }
@@ -0,0 +1,263 @@
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rand::rngs::{OsRng, StdRng};
+use rand::{RngCore, SeedableRng};
+
+/// How long the overflow/clock-regression spin in [`Context::advance`] sleeps between clock
+/// polls. See [`crate::versions::v0::Context::advance`] for the rationale.
+const SPIN_POLL_INTERVAL: Duration = Duration::from_micros(100);
+
+/// Number of low bits of a [`Context`]'s packed state reserved for the counter.
+const COUNTER_BITS: u32 = 20;
+const COUNTER_MASK: u64 = (1 << COUNTER_BITS) - 1;
+
+/// The highest counter value that fits the formatted 4-digit counter field.
+///
+/// See [`crate::versions::v0::MAX_COUNTER_PER_MS`] for the rationale; `SQUIDv1` applies the
+/// same per-millisecond budget.
+pub const MAX_COUNTER_PER_MS: u64 = 9_999;
+
+/// Process-wide generation state that one or more [`SQUIDv1`] instances can share.
+///
+/// Uses the same packed-atomic `(timestamp, counter)` design as
+/// [`crate::versions::v0::Context`], plus a trailing random block (`rand_b`, after the
+/// UUIDv7 field of the same name) that is re-rolled from a CSPRNG every time the
+/// millisecond advances.
+pub struct Context {
+    state: AtomicU64,
+    rand_b: AtomicU32,
+}
+
+impl Context {
+    /// Creates a fresh context with no recorded timestamp.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: AtomicU64::new(0),
+            rand_b: AtomicU32::new(0),
+        }
+    }
+
+    fn pack(timestamp: u64, counter: u64) -> u64 {
+        (timestamp << COUNTER_BITS) | (counter & COUNTER_MASK)
+    }
+
+    fn unpack(packed: u64) -> (u64, u64) {
+        (packed >> COUNTER_BITS, packed & COUNTER_MASK)
+    }
+
+    fn now_millis() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_millis() as u64
+    }
+
+    /// Atomically advances the context to the current millisecond, returning the
+    /// `(timestamp, counter, rand_b)` triple the caller should use for its next generated ID.
+    ///
+    /// Behaves like [`crate::versions::v0::Context::advance`] for the timestamp and counter:
+    /// it only moves the timestamp forward, clamping to `last_timestamp` (and incrementing
+    /// the counter instead) if the clock is unchanged or has moved backward, and spin-waits
+    /// past [`MAX_COUNTER_PER_MS`] instead of overflowing the counter. Whenever the
+    /// timestamp advances, `rand_b` is re-rolled from a CSPRNG; otherwise the previously
+    /// rolled `rand_b` is reused alongside the incremented counter. If the counter would
+    /// exceed [`MAX_COUNTER_PER_MS`] before the clock moves past `last_timestamp`,
+    /// this sleeps for [`SPIN_POLL_INTERVAL`] between clock re-samples rather than peg a
+    /// core while waiting out a clock regression.
+    fn advance(&self) -> (u64, u64, u32) {
+        let mut now = Self::now_millis();
+        let mut current = self.state.load(Ordering::Relaxed);
+        loop {
+            let (last_timestamp, counter) = Self::unpack(current);
+            let (next_timestamp, next_counter, rolls_rand_b) = if now > last_timestamp {
+                (now, 0, true)
+            } else if counter >= MAX_COUNTER_PER_MS {
+                loop {
+                    thread::sleep(SPIN_POLL_INTERVAL);
+                    now = Self::now_millis();
+                    if now > last_timestamp {
+                        break;
+                    }
+                }
+                (now, 0, true)
+            } else {
+                (last_timestamp, counter + 1, false)
+            };
+            let next = Self::pack(next_timestamp, next_counter);
+            match self
+                .state
+                .compare_exchange_weak(current, next, Ordering::SeqCst, Ordering::Relaxed)
+            {
+                Ok(_) => {
+                    let rand_b = if rolls_rand_b {
+                        let fresh = OsRng.next_u32();
+                        self.rand_b.store(fresh, Ordering::Relaxed);
+                        fresh
+                    } else {
+                        self.rand_b.load(Ordering::Relaxed)
+                    };
+                    return (next_timestamp, next_counter, rand_b);
+                }
+                Err(actual) => {
+                    current = actual;
+                    now = Self::now_millis();
+                }
+            }
+        }
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Privacy-preserving version 1 implementation of the SQUID ID generation system.
+///
+/// Unlike [`crate::versions::v0::SQUIDv0`], `SQUIDv1` never touches the machine UUID. It
+/// follows the UUIDv7 layout (`unix_ts_ms | counter | rand_b`), timestamp first, so IDs stay
+/// lexicographically sortable by generation time regardless of which instance produced them.
+/// The device identifier is replaced with a random per-instance node value, seeded once from
+/// a CSPRNG and placed after the counter, and a trailing random block is re-rolled every
+/// millisecond. Two processes on the same host produce unlinkable IDs and nothing in the
+/// output reveals the machine identity, so `SQUIDv1` is safe to expose to the internet.
+pub struct SQUIDv1 {
+    node: u64,
+    context: Arc<Context>,
+}
+
+impl SQUIDv1 {
+    /// Creates a new `SQUIDv1` instance with its own private [`Context`].
+    ///
+    /// The node value is drawn from a CSPRNG ([`OsRng`]) unless `seed` is given, in which
+    /// case it is derived from that seed so tests can reproduce a stable node value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sortable_quick_unique_id::SQUIDv1;
+    ///
+    /// let squid = SQUIDv1::new(None);
+    /// ```
+    #[must_use]
+    pub fn new(seed: Option<u64>) -> Self {
+        Self::with_context(seed, Arc::new(Context::new()))
+    }
+
+    /// Creates a new `SQUIDv1` backed by an explicit, possibly shared, [`Context`].
+    ///
+    /// See [`crate::versions::v0::SQUIDv0::with_context`] for why sharing a `Context` across
+    /// instances (including across threads) keeps every generated ID unique.
+    #[must_use]
+    pub fn with_context(seed: Option<u64>, context: Arc<Context>) -> Self {
+        let node = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed).next_u64(),
+            None => OsRng.next_u64(),
+        };
+        Self { node, context }
+    }
+
+    /// Generates a unique ID.
+    ///
+    /// The ID is a combination of the current timestamp in milliseconds, a counter to ensure
+    /// uniqueness within a millisecond, the random node value, and a trailing random block
+    /// re-rolled every millisecond. The timestamp leads the format (UUIDv7-style) so that IDs
+    /// from different `SQUIDv1` instances still sort lexicographically by generation time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sortable_quick_unique_id::{SQUID, SQUIDv1};
+    ///
+    /// let mut squid = SQUIDv1::new(None);
+    /// let id = squid.generate();
+    /// println!("Generated ID: {}", id);
+    /// ```
+    pub fn generate(&mut self) -> String {
+        let (timestamp, counter, rand_b) = self.context.advance();
+
+        // Format: Timestamp-Counter-Node-RandB
+        format!("{}-{:04}-{:016x}-{:08x}", timestamp, counter, self.node, rand_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Context, SQUIDv1};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_generate_unique_ids() {
+        let mut squid = SQUIDv1::new(None);
+        let mut generated_ids = HashSet::new();
+        let total_ids = 1_000_000;
+
+        for _ in 0..total_ids {
+            let id = squid.generate();
+            assert!(!generated_ids.contains(&id), "Duplicate ID found: {}", id);
+            generated_ids.insert(id);
+        }
+
+        assert_eq!(generated_ids.len(), total_ids, "Not all IDs are unique");
+    }
+
+    #[test]
+    fn test_generate_sorts_by_timestamp_across_instances() {
+        // Different instances get different random nodes, but the timestamp leads the
+        // format, so IDs generated in different milliseconds should still sort in
+        // generation order regardless of which instance produced them. (Within the same
+        // millisecond, ordering across independent instances is unspecified, since their
+        // counters don't coordinate — this only asserts ordering once the clock has ticked.)
+        let mut a = SQUIDv1::new(None);
+        let mut b = SQUIDv1::new(None);
+
+        let first = a.generate();
+        thread::sleep(Duration::from_millis(2));
+        let second = b.generate();
+
+        assert!(
+            first < second,
+            "{} should sort before {} once the clock has advanced",
+            first,
+            second
+        );
+    }
+
+    #[test]
+    fn test_seeded_node_is_reproducible() {
+        let a = SQUIDv1::new(Some(42));
+        let b = SQUIDv1::new(Some(42));
+        assert_eq!(a.node, b.node);
+
+        let c = SQUIDv1::new(Some(7));
+        assert_ne!(a.node, c.node);
+    }
+
+    #[test]
+    fn test_shared_context_is_unique_across_threads() {
+        let context = Arc::new(Context::new());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let mut squid = SQUIDv1::with_context(None, Arc::clone(&context));
+                thread::spawn(move || (0..10_000).map(|_| squid.generate()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut generated_ids = HashSet::new();
+        for handle in handles {
+            for id in handle.join().expect("thread panicked") {
+                assert!(generated_ids.insert(id.clone()), "Duplicate ID found: {}", id);
+            }
+        }
+
+        assert_eq!(generated_ids.len(), 80_000, "Not all IDs are unique");
+    }
+}